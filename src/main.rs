@@ -3,6 +3,77 @@ use regex::Regex;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+/// シェル風のグロブパターン（`*`, `?`, `[...]`, `**`）をアンカー付き正規表現に変換する
+///
+/// # Arguments
+/// * `glob` - 変換対象のグロブパターン
+///
+/// # Returns
+/// `^` と `$` で両端を固定した正規表現文字列
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    regex.push_str(".*");
+                } else {
+                    regex.push_str("[^/\\\\]*");
+                }
+            }
+            '?' => regex.push_str("[^/\\\\]"),
+            '[' => {
+                regex.push('[');
+                for nc in chars.by_ref() {
+                    regex.push(nc);
+                    if nc == ']' {
+                        break;
+                    }
+                }
+            }
+            '.' | '+' | '(' | ')' | '|' | '^' | '$' | '{' | '}' | '\\' => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            _ => regex.push(c),
+        }
+    }
+
+    regex.push('$');
+    regex
+}
+
+/// カンマ区切りのグロブパターンをコンパイル済み正規表現のリストに変換する
+///
+/// # Arguments
+/// * `raw_patterns` - コマンドラインから受け取った生のパターン文字列群
+///
+/// # Returns
+/// コンパイルに成功したパターンの `Regex` のリスト
+fn compile_patterns<'a, I>(raw_patterns: I) -> Vec<Regex>
+where
+    I: Iterator<Item = &'a String>,
+{
+    raw_patterns
+        .flat_map(|pattern| pattern.split(','))
+        .map(|pattern| pattern.trim())
+        .filter(|pattern| !pattern.is_empty())
+        .filter_map(|pattern| {
+            let regex_str = glob_to_regex(pattern);
+            match Regex::new(&regex_str) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    eprintln!("Invalid pattern '{}': {}", pattern, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
 /// アプリケーションの設定を保持する構造体
 #[derive(Debug)]
 struct Config {
@@ -14,6 +85,32 @@ struct Config {
     directories_only: bool,
     /// 除外するパターンのリスト
     exclude_patterns: Vec<Regex>,
+    /// 含めるパターンのリスト（指定された場合、一致するファイルのみ表示）
+    include_patterns: Vec<Regex>,
+    /// フィルタの結果空になったディレクトリを除去するかどうか
+    prune: bool,
+    /// サイズ列を表示するかどうか
+    show_size: bool,
+    /// パーミッション列を表示するかどうか
+    show_permissions: bool,
+    /// 所有者列を表示するかどうか
+    show_owner: bool,
+    /// 最終更新日時列を表示するかどうか
+    show_date: bool,
+    /// ディレクトリ配下の合計サイズを集計して表示するかどうか
+    du_mode: bool,
+    /// あいまい検索のクエリ（指定された場合、一致したエントリとその祖先のみ表示）
+    find_query: Option<String>,
+}
+
+impl Config {
+    /// 何らかの形でメタデータ（サイズ・パーミッション・所有者・日時）を必要とするかどうか
+    ///
+    /// # Returns
+    /// メタデータの取得が必要な場合true
+    fn needs_metadata(&self) -> bool {
+        self.show_size || self.show_permissions || self.show_owner || self.show_date || self.du_mode
+    }
 }
 
 impl Config {
@@ -53,38 +150,439 @@ impl Config {
                     .short('I')
                     .long("exclude")
                     .value_name("PATTERN")
-                    .help("Exclude files/directories matching pattern")
+                    .help("Exclude files/directories matching glob pattern (comma-separated)")
                     .action(clap::ArgAction::Append),
             )
+            .arg(
+                Arg::new("pattern")
+                    .short('P')
+                    .long("pattern")
+                    .value_name("PATTERN")
+                    .help("Only include files matching glob pattern (comma-separated); directories are always listed")
+                    .action(clap::ArgAction::Append),
+            )
+            .arg(
+                Arg::new("prune")
+                    .long("prune")
+                    .help("Remove empty directories from the output")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("size")
+                    .short('s')
+                    .long("size")
+                    .help("Print the size of each file/directory")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("permissions")
+                    .short('p')
+                    .long("permissions")
+                    .help("Print the permissions of each file/directory")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("owner")
+                    .short('u')
+                    .long("owner")
+                    .help("Print the owner of each file/directory")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("date")
+                    .short('D')
+                    .long("date")
+                    .help("Print the last modification date of each file/directory")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("du")
+                    .long("du")
+                    .help("Print cumulative directory sizes (sum of all listed descendants)")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("find")
+                    .long("find")
+                    .value_name("QUERY")
+                    .help("Keep only entries whose name fuzzy-matches QUERY and their ancestor directories, annotated with a relevance score"),
+            )
             .get_matches();
 
         let start_path = PathBuf::from(matches.get_one::<String>("directory").unwrap());
         let max_depth = matches.get_one::<usize>("max-depth").copied();
         let directories_only = matches.get_flag("directories-only");
 
-        let exclude_patterns = if let Some(patterns) = matches.get_many::<String>("exclude") {
-            patterns
-                .filter_map(|pattern| {
-                    match Regex::new(pattern) {
-                        Ok(regex) => Some(regex),
-                        Err(e) => {
-                            eprintln!("Invalid regex pattern '{}': {}", pattern, e);
-                            None
-                        }
-                    }
-                })
-                .collect()
-        } else {
-            Vec::new()
+        let exclude_patterns = match matches.get_many::<String>("exclude") {
+            Some(patterns) => compile_patterns(patterns),
+            None => Vec::new(),
         };
 
+        let include_patterns = match matches.get_many::<String>("pattern") {
+            Some(patterns) => compile_patterns(patterns),
+            None => Vec::new(),
+        };
+
+        let prune = matches.get_flag("prune");
+        let show_size = matches.get_flag("size");
+        let show_permissions = matches.get_flag("permissions");
+        let show_owner = matches.get_flag("owner");
+        let show_date = matches.get_flag("date");
+        let du_mode = matches.get_flag("du");
+        let find_query = matches.get_one::<String>("find").cloned();
+
         Config {
             start_path,
             max_depth,
             directories_only,
             exclude_patterns,
+            include_patterns,
+            prune,
+            show_size,
+            show_permissions,
+            show_owner,
+            show_date,
+            du_mode,
+            find_query,
+        }
+    }
+}
+
+/// ファイル/ディレクトリの追加メタデータ（サイズ・パーミッション・所有者・更新日時）
+#[derive(Debug, Clone)]
+struct NodeMetadata {
+    /// バイト単位のサイズ（`--du`指定時はディレクトリ配下の合計）
+    size: u64,
+    /// 種別文字付きのパーミッション文字列（例: "drwxr-xr-x"）。非Unix環境では簡易表示
+    permissions: String,
+    /// 所有者名（解決できない場合はUIDの文字列表現、非Unix環境では"-"）
+    owner: String,
+    /// 最終更新日時
+    mtime: std::time::SystemTime,
+}
+
+/// バイト数を人間が読みやすい単位（B/K/M/G/T）に変換する
+///
+/// # Arguments
+/// * `bytes` - バイト単位のサイズ
+///
+/// # Returns
+/// 単位付きの文字列
+fn format_size_human(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1}{}", size, UNITS[unit])
+    }
+}
+
+/// Unixのモードビットを`rwxr-xr-x`形式のパーミッション文字列に変換する
+///
+/// # Arguments
+/// * `mode` - `st_mode`のビット列
+///
+/// # Returns
+/// 種別文字と9桁のrwx文字列
+#[cfg(unix)]
+fn format_permissions_unix(mode: u32) -> String {
+    const TRIPLETS: [(u32, char); 9] = [
+        (0o400, 'r'),
+        (0o200, 'w'),
+        (0o100, 'x'),
+        (0o040, 'r'),
+        (0o020, 'w'),
+        (0o010, 'x'),
+        (0o004, 'r'),
+        (0o002, 'w'),
+        (0o001, 'x'),
+    ];
+
+    let type_char = match mode & 0o170000 {
+        0o040000 => 'd',
+        0o120000 => 'l',
+        _ => '-',
+    };
+
+    let mut permissions = String::with_capacity(10);
+    permissions.push(type_char);
+    for (bit, ch) in TRIPLETS {
+        permissions.push(if mode & bit != 0 { ch } else { '-' });
+    }
+    permissions
+}
+
+/// うるう年を考慮したエポック日からの年月日変換（Howard Hinnantのcivil_from_daysアルゴリズム）
+///
+/// # Arguments
+/// * `days_since_epoch` - 1970-01-01からの経過日数
+///
+/// # Returns
+/// (年, 月, 日)
+fn civil_from_days(days_since_epoch: i64) -> (i64, u32, u32) {
+    let z = days_since_epoch + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = if m <= 2 { y + 1 } else { y };
+    (y, m, d)
+}
+
+/// `SystemTime`を`YYYY-MM-DD HH:MM`形式の文字列に変換する
+///
+/// # Arguments
+/// * `time` - 変換対象の時刻
+///
+/// # Returns
+/// フォーマット済みの日時文字列
+fn format_mtime(time: std::time::SystemTime) -> String {
+    let duration = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = duration.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let time_of_day = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        time_of_day / 3600,
+        (time_of_day % 3600) / 60
+    )
+}
+
+/// 名前に含まれる小文字英数字の有無を表すビットマスク（`--find`の早期棄却に使用）
+type CharBag = u64;
+
+/// 文字から`CharBag`上のビット位置を求める（英数字以外は対象外）
+///
+/// # Arguments
+/// * `c` - 対象の文字
+///
+/// # Returns
+/// `a`-`z`は0-25、`0`-`9`は26-35に対応するビット位置
+fn char_bag_bit(c: char) -> Option<u32> {
+    let c = c.to_ascii_lowercase();
+    match c {
+        'a'..='z' => Some(c as u32 - 'a' as u32),
+        '0'..='9' => Some(26 + (c as u32 - '0' as u32)),
+        _ => None,
+    }
+}
+
+/// 文字列に含まれる文字の`CharBag`を計算する
+///
+/// # Arguments
+/// * `s` - 対象の文字列
+///
+/// # Returns
+/// 含まれる英数字ごとに1ビット立てた`CharBag`
+fn char_bag(s: &str) -> CharBag {
+    let mut bag: CharBag = 0;
+    for c in s.chars() {
+        if let Some(bit) = char_bag_bit(c) {
+            bag |= 1 << bit;
+        }
+    }
+    bag
+}
+
+/// 名前がクエリの文字をすべて含む可能性があるかを`CharBag`で安価に判定する
+///
+/// # Arguments
+/// * `name_bag` - 候補名の`CharBag`
+/// * `query_bag` - クエリの`CharBag`
+///
+/// # Returns
+/// クエリの全文字を含みうる場合true（含まない場合は確実にマッチしない）
+fn char_bag_contains(name_bag: CharBag, query_bag: CharBag) -> bool {
+    name_bag & query_bag == query_bag
+}
+
+/// 連続一致でない場合に、一致間のギャップ（スキップ文字数）に応じて与えるスコア
+///
+/// # Arguments
+/// * `gap` - 直前の一致（先頭のクエリ文字では名前の先頭）からスキップした文字数
+///
+/// # Returns
+/// 0.6を起点に1文字スキップごとに0.05ずつ減衰し、0.2で底打ちするスコア
+fn gap_score(gap: usize) -> f64 {
+    (0.6 - 0.05 * gap as f64).max(0.2)
+}
+
+/// クエリ文字列を名前に対してあいまい一致させ、関連度スコアを計算する
+///
+/// 動的計画法でクエリ文字を名前の文字に左から順に対応付け、連続一致や
+/// 単語境界（`/`, `_`, `-`の直後、または小文字→大文字の遷移）での一致を
+/// 優遇し、一致間のギャップにはペナルティを課す。
+///
+/// # Arguments
+/// * `name` - 判定対象の名前
+/// * `query` - 検索クエリ（小文字化済みを期待しない）
+///
+/// # Returns
+/// 一致しない場合は`None`、一致する場合はスコア（大きいほど関連度が高い）
+fn fuzzy_match_score(name: &str, query: &str) -> Option<f64> {
+    if query.is_empty() {
+        return Some(0.0);
+    }
+
+    let query_bag = char_bag(query);
+    if !char_bag_contains(char_bag(name), query_bag) {
+        return None;
+    }
+
+    let name_chars: Vec<char> = name.chars().collect();
+    let query_chars: Vec<char> = query.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let (name_len, query_len) = (name_chars.len(), query_chars.len());
+    if query_len > name_len {
+        return None;
+    }
+
+    // dp[i][j] = クエリの先頭i文字を、名前の先頭j文字の範囲でi文字目がj-1番目の文字に
+    // 一致する形で消費し終えたときの最良スコア（到達不能は負の無限大）
+    // 先頭のクエリ文字も名前の先頭からの距離で評価するため、起点はdp[0][0]のみとする
+    let mut dp = vec![vec![f64::NEG_INFINITY; name_len + 1]; query_len + 1];
+    dp[0][0] = 0.0;
+
+    for i in 1..=query_len {
+        for j in i..=name_len {
+            let name_char = name_chars[j - 1];
+            if name_char.to_ascii_lowercase() != query_chars[i - 1] {
+                continue;
+            }
+
+            let is_boundary = j == 1
+                || matches!(name_chars[j - 2], '/' | '_' | '-')
+                || (name_chars[j - 2].is_lowercase() && name_char.is_uppercase());
+
+            let mut best = f64::NEG_INFINITY;
+            for (offset, &prev_score) in dp[i - 1][(i - 1)..j].iter().enumerate() {
+                if prev_score.is_infinite() {
+                    continue;
+                }
+
+                let k = (i - 1) + offset;
+                let gap = (j - 1).saturating_sub(k);
+                let char_score = if gap == 0 {
+                    1.0
+                } else if is_boundary {
+                    0.8
+                } else {
+                    gap_score(gap)
+                };
+
+                let score = prev_score + char_score;
+                if score > best {
+                    best = score;
+                }
+            }
+
+            dp[i][j] = best;
+        }
+    }
+
+    let best = (query_len..=name_len).fold(f64::NEG_INFINITY, |acc, j| acc.max(dp[query_len][j]));
+
+    if best.is_finite() && best > 0.0 {
+        Some(best)
+    } else {
+        None
+    }
+}
+
+/// あいまい検索クエリに基づいてツリーをフィルタする
+///
+/// ノード自身が一致するか、子孫に一致があるノードのみを残す。子はスコアの
+/// 降順に並び替えられ、各ノードの`find_score`には自身と子孫の最良スコアが
+/// 伝播される。
+///
+/// # Arguments
+/// * `node` - フィルタ対象のノード
+/// * `query` - 検索クエリ
+///
+/// # Returns
+/// 一致が見つからなかった場合は`None`
+fn filter_by_find_query(mut node: TreeNode, query: &str) -> Option<TreeNode> {
+    let own_score = fuzzy_match_score(&node.name, query);
+
+    let mut children: Vec<TreeNode> = std::mem::take(&mut node.children)
+        .into_iter()
+        .filter_map(|child| filter_by_find_query(child, query))
+        .collect();
+
+    children.sort_by(|a, b| {
+        b.find_score
+            .partial_cmp(&a.find_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let best_descendant_score = children.iter().filter_map(|c| c.find_score).fold(None, |acc: Option<f64>, s| {
+        Some(acc.map_or(s, |a| a.max(s)))
+    });
+
+    node.children = children;
+    node.find_score = match (own_score, best_descendant_score) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (Some(a), None) => Some(a),
+        (None, Some(b)) => Some(b),
+        (None, None) => None,
+    };
+
+    node.find_score.map(|_| node)
+}
+
+/// 表示する追加カラムの選択状態
+#[derive(Debug, Clone, Copy)]
+struct ColumnOptions {
+    /// サイズ列を表示するかどうか
+    show_size: bool,
+    /// パーミッション列を表示するかどうか
+    show_permissions: bool,
+    /// 所有者列を表示するかどうか
+    show_owner: bool,
+    /// 日時列を表示するかどうか
+    show_date: bool,
+    /// `--find`の関連度スコアを表示するかどうか
+    show_find_score: bool,
+}
+
+impl ColumnOptions {
+    /// `Config`から`ColumnOptions`を構築する
+    ///
+    /// # Arguments
+    /// * `config` - アプリケーションの設定
+    ///
+    /// # Returns
+    /// 設定から導出されたカラム表示オプション
+    fn from_config(config: &Config) -> Self {
+        ColumnOptions {
+            show_size: config.show_size || config.du_mode,
+            show_permissions: config.show_permissions,
+            show_owner: config.show_owner,
+            show_date: config.show_date,
+            show_find_score: config.find_query.is_some(),
         }
     }
+
+    /// 名前の手前に表示する列（関連度スコアを除く）のいずれかが有効かどうか
+    fn any(&self) -> bool {
+        self.show_size || self.show_permissions || self.show_owner || self.show_date
+    }
 }
 
 /// ツリー構造のノードを表現する構造体
@@ -96,6 +594,10 @@ struct TreeNode {
     is_dir: bool,
     /// 子ノードのリスト
     children: Vec<TreeNode>,
+    /// サイズ・パーミッション等の追加メタデータ
+    metadata: Option<NodeMetadata>,
+    /// `--find`指定時の関連度スコア（自身または子孫の最良スコア）
+    find_score: Option<f64>,
 }
 
 impl TreeNode {
@@ -113,6 +615,8 @@ impl TreeNode {
             name,
             is_dir,
             children: Vec::new(),
+            metadata: None,
+            find_score: None,
         }
     }
 
@@ -124,6 +628,54 @@ impl TreeNode {
         self.children.push(child);
     }
 
+    /// 追加カラム（パーミッション・所有者・サイズ・日時）を角括弧で連結した文字列を組み立てる
+    ///
+    /// # Arguments
+    /// * `columns` - 表示する列の選択状態
+    ///
+    /// # Returns
+    /// 名前の手前に差し込むカラム文字列（列が無ければ空文字列）
+    fn format_columns(&self, columns: &ColumnOptions) -> String {
+        if !columns.any() {
+            return String::new();
+        }
+
+        let Some(meta) = &self.metadata else {
+            return String::new();
+        };
+
+        let mut out = String::new();
+        if columns.show_permissions {
+            out.push_str(&format!("[{}] ", meta.permissions));
+        }
+        if columns.show_owner {
+            out.push_str(&format!("[{:>8}] ", meta.owner));
+        }
+        if columns.show_size {
+            out.push_str(&format!("[{:>8}] ", format_size_human(meta.size)));
+        }
+        if columns.show_date {
+            out.push_str(&format!("[{}] ", format_mtime(meta.mtime)));
+        }
+        out
+    }
+
+    /// `--find`の関連度スコアを名前の後ろに付与する文字列を組み立てる
+    ///
+    /// # Arguments
+    /// * `columns` - 表示する列の選択状態
+    ///
+    /// # Returns
+    /// スコア表示が不要な場合は空文字列
+    fn format_score_suffix(&self, columns: &ColumnOptions) -> String {
+        if columns.show_find_score {
+            if let Some(score) = self.find_score {
+                return format!(" ({:.2})", score);
+            }
+        }
+        String::new()
+    }
+
     /// ツリー構造を表示する
     ///
     /// # Arguments
@@ -132,11 +684,27 @@ impl TreeNode {
     /// * `file_count` - ファイル数のカウンタ
     /// * `dir_count` - ディレクトリ数のカウンタ
     /// * `show_files` - ファイルを表示するかどうか
-    fn display(&self, prefix: &str, is_last: bool, file_count: &mut usize, dir_count: &mut usize, show_files: bool) {
+    /// * `columns` - 表示する追加列の選択状態
+    fn display(
+        &self,
+        prefix: &str,
+        is_last: bool,
+        file_count: &mut usize,
+        dir_count: &mut usize,
+        show_files: bool,
+        columns: &ColumnOptions,
+    ) {
         // ルートディレクトリ以外を表示
         if !prefix.is_empty() {
             let connector = if is_last { "└── " } else { "├── " };
-            println!("{}{}{}", prefix, connector, self.name);
+            println!(
+                "{}{}{}{}{}",
+                prefix,
+                connector,
+                self.format_columns(columns),
+                self.name,
+                self.format_score_suffix(columns)
+            );
         }
 
         // 統計情報の更新（ルート以外）
@@ -157,16 +725,67 @@ impl TreeNode {
 
         for (i, child) in self.children.iter().enumerate() {
             let is_child_last = i == self.children.len() - 1;
-            child.display(&child_prefix, is_child_last, file_count, dir_count, show_files);
+            child.display(&child_prefix, is_child_last, file_count, dir_count, show_files, columns);
         }
     }
 }
 
+/// ディレクトリ探索において、子要素をどの範囲まで訪問すべきかを表す判定結果
+#[derive(Debug, Clone)]
+enum VisitChildrenSet {
+    /// 子要素を一切訪問しない（`read_dir` すら呼び出さない）
+    Empty,
+    /// 子要素は訪問するが、配下に一致する要素がある保証はない
+    This,
+    /// 指定した名前の子要素のみ訪問する
+    #[allow(dead_code)]
+    Set(HashSet<String>),
+    /// 配下はすべて対象となるため、以降はパターン評価を行わない
+    Recursive,
+}
+
+/// ディレクトリに対する訪問可否を判定するトレイト
+///
+/// `build_tree_recursive` は `read_dir` を呼ぶ前にこの判定を仰ぐことで、
+/// 除外対象のサブツリーに対するシステムコールを丸ごと省略できる。
+trait Matcher {
+    /// 指定したディレクトリについて、訪問すべき子要素の範囲を返す
+    ///
+    /// # Arguments
+    /// * `dir_path` - 判定対象のディレクトリパス
+    fn visit_children(&self, dir_path: &Path) -> VisitChildrenSet;
+}
+
+/// exclude/include パターンに基づいて訪問可否を判定するMatcher実装
+struct PatternMatcher<'a> {
+    exclude_patterns: &'a [Regex],
+    include_patterns: &'a [Regex],
+}
+
+impl Matcher for PatternMatcher<'_> {
+    fn visit_children(&self, dir_path: &Path) -> VisitChildrenSet {
+        // パターンが一つも設定されていなければ、配下を評価する意味がない
+        if self.exclude_patterns.is_empty() && self.include_patterns.is_empty() {
+            return VisitChildrenSet::Recursive;
+        }
+
+        if let Some(name) = dir_path.file_name() {
+            let name = name.to_string_lossy();
+            if self.exclude_patterns.iter().any(|pattern| pattern.is_match(&name)) {
+                return VisitChildrenSet::Empty;
+            }
+        }
+
+        VisitChildrenSet::This
+    }
+}
+
 /// ツリー構造の表示を行うメイン構造体
 struct TreePrinter {
     config: Config,
-    /// 除外されたパスのセット
-    excluded_paths: HashSet<PathBuf>,
+    /// UIDから所有者名への解決結果のキャッシュ
+    #[cfg(unix)]
+    owner_cache: std::collections::HashMap<u32, String>,
 }
 
 impl TreePrinter {
@@ -180,7 +799,72 @@ impl TreePrinter {
     fn new(config: Config) -> Self {
         TreePrinter {
             config,
-            excluded_paths: HashSet::new(),
+            #[cfg(unix)]
+            owner_cache: std::collections::HashMap::new(),
+        }
+    }
+
+    /// UIDに対応する所有者名を解決する（`id -nu`の結果をキャッシュする）
+    ///
+    /// # Arguments
+    /// * `uid` - 解決対象のUID
+    ///
+    /// # Returns
+    /// 解決できた場合はユーザー名、できなければUIDの文字列表現
+    #[cfg(unix)]
+    fn resolve_owner(&mut self, uid: u32) -> String {
+        if let Some(name) = self.owner_cache.get(&uid) {
+            return name.clone();
+        }
+
+        let name = std::process::Command::new("id")
+            .args(["-nu", &uid.to_string()])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| uid.to_string());
+
+        self.owner_cache.insert(uid, name.clone());
+        name
+    }
+
+    /// `fs::Metadata`から表示用の`NodeMetadata`を構築する
+    ///
+    /// # Arguments
+    /// * `metadata` - 変換元のファイルシステムメタデータ
+    ///
+    /// # Returns
+    /// 表示・集計に使う`NodeMetadata`
+    fn build_node_metadata(&mut self, metadata: &std::fs::Metadata) -> NodeMetadata {
+        let size = metadata.len();
+        let mtime = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+
+        #[cfg(unix)]
+        let (permissions, owner) = {
+            use std::os::unix::fs::MetadataExt;
+            (
+                format_permissions_unix(metadata.mode()),
+                self.resolve_owner(metadata.uid()),
+            )
+        };
+
+        #[cfg(not(unix))]
+        let (permissions, owner) = {
+            let perm_str = if metadata.permissions().readonly() {
+                "r---------".to_string()
+            } else {
+                "rw--------".to_string()
+            };
+            (perm_str, "-".to_string())
+        };
+
+        NodeMetadata {
+            size,
+            permissions,
+            owner,
+            mtime,
         }
     }
 
@@ -200,20 +884,23 @@ impl TreePrinter {
         false
     }
 
-    /// パスが除外されたパスの子要素かどうかを判定する
+    /// ファイル名が含めるパターンに一致するかどうかを判定する
     ///
     /// # Arguments
-    /// * `path` - 判定対象のパス
+    /// * `file_name` - 判定対象のファイル名
     ///
     /// # Returns
-    /// 除外されたパスの子要素の場合true
-    fn is_descendant_of_excluded(&self, path: &Path) -> bool {
-        for excluded in &self.excluded_paths {
-            if path.starts_with(excluded) && path != excluded {
-                return true;
-            }
+    /// 含めるパターンが設定されていない場合は常にtrue、
+    /// 設定されている場合はいずれかのパターンに一致すればtrue
+    fn should_include(&self, file_name: &str) -> bool {
+        if self.config.include_patterns.is_empty() {
+            return true;
         }
-        false
+
+        self.config
+            .include_patterns
+            .iter()
+            .any(|pattern| pattern.is_match(file_name))
     }
 
     /// ディレクトリ構造を再帰的に構築する
@@ -221,10 +908,16 @@ impl TreePrinter {
     /// # Arguments
     /// * `dir_path` - 探索するディレクトリのパス
     /// * `current_depth` - 現在の深度
+    /// * `skip_patterns` - 親側で`Recursive`判定済みのため、以降パターン評価を省略するかどうか
     ///
     /// # Returns
     /// 構築されたTreeNodeのオプション
-    fn build_tree_recursive(&mut self, dir_path: &Path, current_depth: usize) -> Option<TreeNode> {
+    fn build_tree_recursive(
+        &mut self,
+        dir_path: &Path,
+        current_depth: usize,
+        skip_patterns: bool,
+    ) -> Option<TreeNode> {
         // 最大深度チェック
         if let Some(max_depth) = self.config.max_depth {
             if current_depth > max_depth {
@@ -232,10 +925,26 @@ impl TreePrinter {
             }
         }
 
-        // 除外されたパスの子要素かチェック
-        if self.is_descendant_of_excluded(dir_path) {
-            return None;
-        }
+        // このディレクトリ自体の訪問可否と、以降のパターン評価要否を判定する
+        let mut allowed_names: Option<HashSet<String>> = None;
+        let skip_patterns = if skip_patterns {
+            true
+        } else {
+            let matcher = PatternMatcher {
+                exclude_patterns: &self.config.exclude_patterns,
+                include_patterns: &self.config.include_patterns,
+            };
+
+            match matcher.visit_children(dir_path) {
+                VisitChildrenSet::Empty => return None,
+                VisitChildrenSet::Recursive => true,
+                VisitChildrenSet::Set(names) => {
+                    allowed_names = Some(names);
+                    false
+                }
+                VisitChildrenSet::This => false,
+            }
+        };
 
         let name = if dir_path == self.config.start_path {
             dir_path.display().to_string()
@@ -245,6 +954,12 @@ impl TreePrinter {
 
         let mut node = TreeNode::new(name, true);
 
+        if self.config.needs_metadata() {
+            if let Ok(meta) = std::fs::metadata(dir_path) {
+                node.metadata = Some(self.build_node_metadata(&meta));
+            }
+        }
+
         // ディレクトリの内容を読み取り
         let mut entries = match std::fs::read_dir(dir_path) {
             Ok(entries) => {
@@ -265,11 +980,11 @@ impl TreePrinter {
             let is_dir = path.is_dir();
             let file_name = entry.file_name().to_string_lossy().to_string();
 
-            // 除外パターンのチェック
-            if self.should_exclude(&file_name) {
-                // 除外されたパスを記録
-                self.excluded_paths.insert(path.clone());
-                continue;
+            // Set判定された場合は、指定された名前以外をスキップ
+            if let Some(allowed) = &allowed_names {
+                if !allowed.contains(&file_name) {
+                    continue;
+                }
             }
 
             // ディレクトリ専用モードでファイルをスキップ
@@ -278,17 +993,53 @@ impl TreePrinter {
             }
 
             if is_dir {
-                // 再帰的にサブディレクトリを処理
-                if let Some(child_node) = self.build_tree_recursive(&path, current_depth + 1) {
+                // 再帰的にサブディレクトリを処理（除外判定はMatcherに委ねる）
+                if let Some(child_node) =
+                    self.build_tree_recursive(&path, current_depth + 1, skip_patterns)
+                {
                     node.add_child(child_node);
                 }
             } else {
+                if !skip_patterns {
+                    // 除外パターンのチェック
+                    if self.should_exclude(&file_name) {
+                        continue;
+                    }
+
+                    // 含めるパターンに一致しないファイルはスキップ
+                    if !self.should_include(&file_name) {
+                        continue;
+                    }
+                }
+
                 // ファイルノードを追加
-                let file_node = TreeNode::new(file_name, false);
+                let mut file_node = TreeNode::new(file_name, false);
+                if self.config.needs_metadata() {
+                    if let Ok(meta) = entry.metadata() {
+                        file_node.metadata = Some(self.build_node_metadata(&meta));
+                    }
+                }
                 node.add_child(file_node);
             }
         }
 
+        // --du指定時は子要素のサイズを合計してディレクトリの表示サイズとする
+        if self.config.du_mode {
+            let total: u64 = node
+                .children
+                .iter()
+                .map(|child| child.metadata.as_ref().map(|m| m.size).unwrap_or(0))
+                .sum();
+            if let Some(meta) = node.metadata.as_mut() {
+                meta.size = total;
+            }
+        }
+
+        // フィルタの結果空になったディレクトリを除去（開始パス自身は除く）
+        if self.config.prune && node.children.is_empty() && dir_path != self.config.start_path {
+            return None;
+        }
+
         Some(node)
     }
 
@@ -296,32 +1047,53 @@ impl TreePrinter {
     fn display_tree(&mut self) {
         let start_path = self.config.start_path.clone();
         let show_files = !self.config.directories_only;
-        
-        if let Some(root) = self.build_tree_recursive(&start_path, 0) {
+        let columns = ColumnOptions::from_config(&self.config);
+
+        if let Some(mut root) = self.build_tree_recursive(&start_path, 0, false) {
+            // あいまい検索クエリが指定されていれば、一致したエントリとその祖先のみ残す
+            if let Some(query) = self.config.find_query.clone() {
+                let mut children: Vec<TreeNode> = std::mem::take(&mut root.children)
+                    .into_iter()
+                    .filter_map(|child| filter_by_find_query(child, &query))
+                    .collect();
+                children.sort_by(|a, b| {
+                    b.find_score
+                        .partial_cmp(&a.find_score)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+                root.children = children;
+            }
+
             let mut file_count = 0;
             let mut dir_count = 0;
 
             // ルートディレクトリ名を表示
-            println!("{}", root.name);
+            println!("{}{}", root.format_columns(&columns), root.name);
 
             // 子ノードを表示（ルートの子供は常に表示）
             for (i, child) in root.children.iter().enumerate() {
                 let is_last = i == root.children.len() - 1;
                 let connector = if is_last { "└── " } else { "├── " };
-                println!("{}{}", connector, child.name);
-                
+                println!(
+                    "{}{}{}{}",
+                    connector,
+                    child.format_columns(&columns),
+                    child.name,
+                    child.format_score_suffix(&columns)
+                );
+
                 // 統計情報の更新
                 if child.is_dir {
                     dir_count += 1;
                 } else if show_files {
                     file_count += 1;
                 }
-                
+
                 // 子ノードの子を表示
                 let child_prefix = if is_last { "    " } else { "│   " };
                 for (j, grandchild) in child.children.iter().enumerate() {
                     let is_grandchild_last = j == child.children.len() - 1;
-                    grandchild.display(&child_prefix, is_grandchild_last, &mut file_count, &mut dir_count, show_files);
+                    grandchild.display(&child_prefix, is_grandchild_last, &mut file_count, &mut dir_count, show_files, &columns);
                 }
             }
 